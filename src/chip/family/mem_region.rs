@@ -0,0 +1,73 @@
+/// A contiguous region of memory, described by its origin and length.
+///
+/// Used both to describe a chip's flash/RAM layout for `memory.x` generation,
+/// and as the building block for splitting flash into named partitions
+/// (bootloader, application, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemRegion {
+    pub flash_origin: u32,
+    pub flash_length: u32,
+    pub ram_origin: u32,
+    pub ram_length: u32,
+}
+
+/// Flash partitions carved out of a [`MemRegion`] for an `embassy-boot`
+/// bootloader + OTA firmware-updater setup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partitions {
+    pub bootloader: MemRegion,
+    pub bootloader_state: MemRegion,
+    pub active: MemRegion,
+    pub dfu: MemRegion,
+}
+
+impl MemRegion {
+    const SECTOR_SIZE: u32 = 0x1000;
+    const BOOTLOADER_LEN: u32 = 0x0002_0000;
+    const BOOTLOADER_STATE_LEN: u32 = Self::SECTOR_SIZE;
+
+    /// Split this region's flash into BOOTLOADER / BOOTLOADER_STATE / ACTIVE /
+    /// DFU partitions.
+    ///
+    /// The bootloader sits at flash origin, followed by its state sector.
+    /// The remaining flash is split evenly (sector-aligned) between ACTIVE
+    /// and DFU, since DFU must be able to hold a full copy of the image
+    /// running from ACTIVE.
+    pub fn partitions(&self) -> Partitions {
+        let bootloader = MemRegion {
+            flash_origin: self.flash_origin,
+            flash_length: Self::BOOTLOADER_LEN,
+            ..*self
+        };
+
+        let bootloader_state = MemRegion {
+            flash_origin: bootloader.flash_origin + bootloader.flash_length,
+            flash_length: Self::BOOTLOADER_STATE_LEN,
+            ..*self
+        };
+
+        let remaining =
+            self.flash_length - Self::BOOTLOADER_LEN - Self::BOOTLOADER_STATE_LEN;
+        let active_length = (remaining / 2) & !(Self::SECTOR_SIZE - 1);
+        let dfu_length = remaining - active_length;
+
+        let active = MemRegion {
+            flash_origin: bootloader_state.flash_origin + bootloader_state.flash_length,
+            flash_length: active_length,
+            ..*self
+        };
+
+        let dfu = MemRegion {
+            flash_origin: active.flash_origin + active.flash_length,
+            flash_length: dfu_length,
+            ..*self
+        };
+
+        Partitions {
+            bootloader,
+            bootloader_state,
+            active,
+            dfu,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+pub mod mem_region;
+
+use std::fmt::{self, Display, Formatter};
+
+use mem_region::MemRegion;
+
+/// The chip family a given chip name belongs to, along with whatever extra
+/// data the family needs to scaffold a project (e.g. the nRF memory layout
+/// for `memory.x`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Family {
+    STM32,
+    NRF(MemRegion),
+    RP,
+}
+
+impl Family {
+    pub(crate) fn from_chip_name(name: &str) -> Option<Self> {
+        if name.starts_with("stm32") {
+            Some(Self::STM32)
+        } else if name.starts_with("rp2040") {
+            Some(Self::RP)
+        } else if name.starts_with("nrf52840") {
+            Some(Self::NRF(MemRegion {
+                flash_origin: 0x0000_0000,
+                flash_length: 0x0010_0000,
+                ram_origin: 0x2000_0000,
+                ram_length: 0x0004_0000,
+            }))
+        } else if name.starts_with("nrf52") {
+            Some(Self::NRF(MemRegion {
+                flash_origin: 0x0000_0000,
+                flash_length: 0x0008_0000,
+                ram_origin: 0x2000_0000,
+                ram_length: 0x0001_0000,
+            }))
+        } else if name.starts_with("nrf9160") {
+            Some(Self::NRF(MemRegion {
+                flash_origin: 0x0000_0000,
+                flash_length: 0x0010_0000,
+                ram_origin: 0x2000_0000,
+                ram_length: 0x0003_0000,
+            }))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Family {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::STM32 => write!(f, "embassy-stm32"),
+            Self::NRF(_) => write!(f, "embassy-nrf"),
+            Self::RP => write!(f, "embassy-rp"),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+pub mod family;
+pub mod target;
+
+use std::str::FromStr;
+
+use family::Family;
+use target::Target;
+
+use crate::error::{Error, InvalidChip};
+
+/// A resolved target chip: its full name, family, and Rust target triple.
+pub struct Chip {
+    pub name: String,
+    pub family: Family,
+    pub target: Target,
+}
+
+impl FromStr for Chip {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let family = Family::from_chip_name(name).ok_or(Error::InvalidChip(InvalidChip::Unknown))?;
+        let target = Target::from_family(&family);
+
+        Ok(Self {
+            name: name.to_owned(),
+            family,
+            target,
+        })
+    }
+}
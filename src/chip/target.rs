@@ -0,0 +1,28 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::family::Family;
+
+/// The Rust target triple a chip is compiled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Thumbv7em,
+    Thumbv6m,
+}
+
+impl Target {
+    pub(crate) fn from_family(family: &Family) -> Self {
+        match family {
+            Family::STM32 | Family::NRF(_) => Self::Thumbv7em,
+            Family::RP => Self::Thumbv6m,
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Thumbv7em => write!(f, "thumbv7em-none-eabihf"),
+            Self::Thumbv6m => write!(f, "thumbv6m-none-eabi"),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use thiserror::Error as ThisError;
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("Invalid chip: {0:?}")]
+    InvalidChip(InvalidChip),
+
+    #[error("Softdevice support is only available on nRF chips")]
+    ErroneousSoftdevice,
+
+    #[error("Bootloader scaffolding is only available on nRF chips")]
+    ErroneousBootloader,
+
+    #[error("--ble-role requires --softdevice")]
+    ErroneousBleRole,
+
+    #[error("--time-driver {0} is not valid for this chip family")]
+    ErroneousTimeDriver(String),
+
+    #[error("Failed to create the cargo project")]
+    CreateCargo,
+
+    #[error("Failed to change the current directory")]
+    ChangeDir,
+
+    #[error("Failed to create folder: {0}")]
+    CreateFolder(String),
+
+    #[error("Failed to create file: {0}")]
+    CreateFile(String),
+
+    #[error("Failed to add dependency: {0}")]
+    CargoAdd(String),
+}
+
+#[derive(Debug)]
+pub enum InvalidChip {
+    Unknown,
+}
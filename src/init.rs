@@ -1,11 +1,17 @@
 use crate::{
     chip::{
-        family::{mem_region::MemRegion, Family},
+        family::{
+            mem_region::{MemRegion, Partitions},
+            Family,
+        },
         target::Target,
         Chip,
     },
     error::{Error, InvalidChip},
-    parser::init_args::{panic_handler::PanicHandler, soft_device::Softdevice, InitArgs},
+    parser::init_args::{
+        ble_role::BleRole, logger::Logger, panic_handler::PanicHandler, soft_device::Softdevice,
+        tick_hz::TickHz, trace::Trace, InitArgs,
+    },
 };
 use indicatif::ProgressBar;
 use inflector::cases::snakecase::to_snake_case;
@@ -51,24 +57,79 @@ impl Init {
             return Err(Error::ErroneousSoftdevice);
         }
 
+        // validate bootloader <--> nrf
+        if args.bootloader && !matches!(chip.family, Family::NRF(_)) {
+            return Err(Error::ErroneousBootloader);
+        }
+
+        // validate ble-role <--> softdevice
+        if args.ble_role.is_some() && args.softdevice.is_none() {
+            return Err(Error::ErroneousBleRole);
+        }
+
+        // validate time-driver <--> family
+        if let Some(time_driver) = &args.time_driver {
+            let valid = match chip.family {
+                Family::STM32 => {
+                    time_driver == "any"
+                        || time_driver
+                            .strip_prefix("tim")
+                            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+                }
+                Family::NRF(_) => matches!(time_driver.as_str(), "rtc0" | "rtc1"),
+                Family::RP => false,
+            };
+
+            if !valid {
+                return Err(Error::ErroneousTimeDriver(time_driver.clone()));
+            }
+        }
+
+        let ble_role = args.ble_role.unwrap_or(BleRole::Peripheral);
+
         self.create_project(&args.name)?;
 
-        self.init_config(&chip.target, &probe_target_name)?;
+        self.init_config(&chip.target, &probe_target_name, &args.logger)?;
+        self.init_trace_config(args.trace.as_ref())?;
         self.init_toolchain(&chip.target)?;
-        self.init_embed(&probe_target_name)?;
-        self.init_build(&chip.family)?;
+        self.init_embed(&probe_target_name, &args.logger)?;
+        self.init_build(&chip.family, &args.logger)?;
         self.init_manifest(
             &args.name,
             &chip,
             &args.panic_handler,
             args.softdevice.as_ref(),
+            args.bootloader,
+            &args.logger,
+            args.trace.as_ref(),
+            ble_role,
+            &args.tick_hz,
+            args.time_driver.as_deref(),
         )?;
         self.init_fmt()?;
-        self.init_main(&chip.family, &args.panic_handler, args.softdevice.as_ref())?;
+        self.init_trace(args.trace.as_ref())?;
+        self.init_main(
+            &chip.family,
+            &args.panic_handler,
+            args.softdevice.as_ref(),
+            args.bootloader,
+            args.trace.as_ref(),
+            ble_role,
+        )?;
 
         if let Family::NRF(mem_reg) = chip.family {
-            self.init_memory_x(mem_reg)?;
-            self.pb.println("[ACTION NEEDED] You must now flash the Softdevice and configure memory.x. Instructions can be found here: https://github.com/embassy-rs/nrf-softdevice#running-examples.");
+            if args.bootloader {
+                let partitions = mem_reg.partitions();
+                self.init_memory_x(partitions.active)?;
+                self.init_bootloader(&args.name, &chip, &partitions)?;
+                self.pb.println("[ACTION NEEDED] You must now flash the bootloader (see ./bootloader) before flashing the app. Instructions can be found here: https://github.com/embassy-rs/embassy/tree/main/embassy-boot.");
+            } else {
+                self.init_memory_x(mem_reg)?;
+            }
+
+            if args.softdevice.is_some() {
+                self.pb.println("[ACTION NEEDED] You must now flash the Softdevice and configure memory.x. Instructions can be found here: https://github.com/embassy-rs/nrf-softdevice#running-examples.");
+            }
         }
 
         Ok(())
@@ -100,19 +161,50 @@ impl Init {
         }
     }
 
-    fn init_config(&self, target: &Target, chip: &str) -> Result<(), Error> {
+    fn init_config(&self, target: &Target, chip: &str, logger: &Logger) -> Result<(), Error> {
         fs::create_dir_all(".cargo").map_err(|_| Error::CreateFolder(".cargo".into()))?;
 
         self.create_file(
             ".cargo/config.toml",
-            &format!(
-                include_str!("templates/config.toml.template"),
-                target = target,
-                chip = chip
-            ),
+            &if logger.needs_rtt() {
+                format!(
+                    include_str!("templates/config.toml.rtt.template"),
+                    target = target,
+                    chip = chip
+                )
+            } else {
+                format!(
+                    include_str!("templates/config.toml.template"),
+                    target = target,
+                    chip = chip
+                )
+            },
         )
     }
 
+    /// Add the SystemView RTT buffer size to `.cargo/config.toml`'s `[env]`
+    /// section, creating that section if the chosen logger didn't already.
+    fn init_trace_config(&self, trace: Option<&Trace>) -> Result<(), Error> {
+        if trace.is_none() {
+            return Ok(());
+        }
+
+        let mut buf = String::new();
+        fs::OpenOptions::new()
+            .read(true)
+            .open(".cargo/config.toml")
+            .and_then(|mut file| file.read_to_string(&mut buf))
+            .map_err(|_| Error::CreateFile(".cargo/config.toml".into()))?;
+
+        buf = if buf.contains("[env]") {
+            buf.replacen("[env]", "[env]\nSYSTEMVIEW_RTT_BUFFER_SIZE = \"4096\"", 1)
+        } else {
+            format!("{buf}\n[env]\nSYSTEMVIEW_RTT_BUFFER_SIZE = \"4096\"\n")
+        };
+
+        self.create_file(".cargo/config.toml", &buf)
+    }
+
     fn init_toolchain(&self, target: &Target) -> Result<(), Error> {
         self.create_file(
             "rust-toolchain.toml",
@@ -123,20 +215,45 @@ impl Init {
         )
     }
 
-    fn init_embed(&self, chip: &str) -> Result<(), Error> {
+    fn init_embed(&self, chip: &str, logger: &Logger) -> Result<(), Error> {
         self.create_file(
             "Embed.toml",
-            &format!(include_str!("templates/Embed.toml.template"), chip = chip),
+            &if logger.needs_rtt() {
+                format!(
+                    include_str!("templates/Embed.toml.rtt.template"),
+                    chip = chip
+                )
+            } else {
+                format!(include_str!("templates/Embed.toml.template"), chip = chip)
+            },
         )
     }
 
-    fn init_build(&self, family: &Family) -> Result<(), Error> {
-        let template = match family {
-            Family::STM32 => include_str!("templates/build.rs.stm32.template"),
-            Family::NRF(_) => include_str!("templates/build.rs.nrf.template"),
+    fn init_build(&self, family: &Family, logger: &Logger) -> Result<(), Error> {
+        // `defmt.x` only exists once the `defmt` crate is actually a dependency;
+        // linking against it unconditionally breaks `--logger log`/`--logger none`.
+        let defmt_link_arg = if logger.needs_rtt() {
+            "    println!(\"cargo:rustc-link-arg-bins=-Tdefmt.x\");\n"
+        } else {
+            ""
+        };
+
+        let content = match family {
+            Family::STM32 => format!(
+                include_str!("templates/build.rs.stm32.template"),
+                defmt_link_arg = defmt_link_arg
+            ),
+            Family::NRF(_) => format!(
+                include_str!("templates/build.rs.nrf.template"),
+                defmt_link_arg = defmt_link_arg
+            ),
+            Family::RP => format!(
+                include_str!("templates/build.rs.rp.template"),
+                defmt_link_arg = defmt_link_arg
+            ),
         };
 
-        self.create_file("build.rs", template)
+        self.create_file("build.rs", &content)
     }
 
     fn init_manifest(
@@ -145,21 +262,31 @@ impl Init {
         chip: &Chip,
         panic_handler: &PanicHandler,
         softdevice: Option<&Softdevice>,
+        bootloader: bool,
+        logger: &Logger,
+        trace: Option<&Trace>,
+        ble_role: BleRole,
+        tick_hz: &TickHz,
+        time_driver: Option<&str>,
     ) -> Result<(), Error> {
         self.create_file(
             "Cargo.toml",
             &format!(include_str!("templates/Cargo.toml.template"), name = name),
         )?;
 
+        let mut executor_features = vec!["arch-cortex-m", "executor-thread", "integrated-timers"];
+        if trace.is_some() {
+            executor_features.push("rtos-trace");
+        }
+
         // NOTE: should be threaded proably
-        self.cargo_add(
-            "embassy-executor",
-            Some(&["arch-cortex-m", "executor-thread", "integrated-timers"]),
-            false,
-        )?;
+        self.cargo_add("embassy-executor", Some(&executor_features), false)?;
         self.cargo_add("embassy-sync", None, false)?;
         self.cargo_add("embassy-futures", None, false)?;
-        self.cargo_add("embassy-time", Some(&["tick-hz-32_768"]), false)?;
+        self.cargo_add("embassy-time", Some(&[tick_hz.str()]), false)?;
+
+        let stm32_time_driver = format!("time-driver-{}", time_driver.unwrap_or("any"));
+        let nrf_time_driver = format!("time-driver-{}", time_driver.unwrap_or("rtc1"));
 
         match chip.family {
             Family::STM32 => self.cargo_add(
@@ -167,7 +294,7 @@ impl Init {
                 Some(&[
                     "memory-x",
                     chip.name.as_str(),
-                    "time-driver-any",
+                    stm32_time_driver.as_str(),
                     "exti",
                     "unstable-pac",
                 ]),
@@ -175,26 +302,48 @@ impl Init {
             ),
             Family::NRF(_) => self.cargo_add(
                 "embassy-nrf",
-                Some(&[chip.name.as_str(), "gpiote", "time-driver-rtc1"]),
+                Some(&[chip.name.as_str(), "gpiote", nrf_time_driver.as_str()]),
                 false,
             ),
+            Family::RP => {
+                self.cargo_add(
+                    "embassy-rp",
+                    Some(&["time-driver", "critical-section-impl"]),
+                    false,
+                )?;
+                // `embassy-rp`'s own `Flash` driver implements these traits directly;
+                // they're real separate crates, not `embassy-rp` features.
+                self.cargo_add("embedded-storage", None, false)?;
+                self.cargo_add("embedded-storage-async", None, false)?;
+                self.cargo_add("rp2040-boot2", None, false)
+            }
         }?;
 
         if let Some(softdevice) = softdevice {
-            self.cargo_add(
-                "nrf-softdevice",
-                Some(&[
-                    chip.name.as_str(),
-                    softdevice.str(),
-                    "ble-peripheral",
-                    "ble-gatt-server",
-                    "critical-section-impl",
-                ]),
-                false,
-            )?;
+            let mut features = vec![chip.name.as_str(), softdevice.str()];
+            features.extend(ble_role.features());
+            features.push("critical-section-impl");
+
+            self.cargo_add("nrf-softdevice", Some(&features), false)?;
             self.cargo_add(&format!("nrf-softdevice-{}", softdevice.str()), None, false)?;
         }
 
+        if bootloader {
+            // Pinned: the generated app template uses `FirmwareUpdater::default()`,
+            // which later `embassy-boot` releases replaced with a
+            // `FirmwareUpdaterConfig`-based constructor. Bump the pin and the
+            // template together, not independently.
+            self.cargo_add_pinned("embassy-boot", None, false, Some("0.2"))?;
+            self.cargo_add_pinned("embassy-boot-nrf", None, false, Some("0.2"))?;
+            self.cargo_add("embedded-storage", None, false)?;
+            self.cargo_add("embedded-storage-async", None, false)?;
+        }
+
+        if trace.is_some() {
+            self.cargo_add("rtos-trace", None, false)?;
+            self.cargo_add("systemview-target", None, false)?;
+        }
+
         self.cargo_add(
             "cortex-m",
             Some(if softdevice.is_some() {
@@ -205,9 +354,22 @@ impl Init {
             false,
         )?;
         self.cargo_add("cortex-m-rt", None, false)?;
-        self.cargo_add("defmt", None, true)?;
-        self.cargo_add("defmt-rtt", None, true)?;
-        self.cargo_add("panic-probe", Some(&["print-defmt"]), true)?;
+
+        match logger {
+            Logger::DefmtRtt => {
+                self.cargo_add("defmt", None, true)?;
+                self.cargo_add("defmt-rtt", None, true)?;
+                self.cargo_add("panic-probe", Some(&["print-defmt"]), true)?;
+            }
+            Logger::Log => {
+                self.cargo_add("log", None, true)?;
+                self.cargo_add("panic-probe", None, false)?;
+            }
+            Logger::None => {
+                self.cargo_add("panic-probe", None, false)?;
+            }
+        }
+
         self.cargo_add(panic_handler.str(), None, false)?;
 
         let mut file = fs::OpenOptions::new()
@@ -220,9 +382,17 @@ impl Init {
         // somewhere between cargo 1.72 and 1.76 the behavior of "cargo add" changed
         let mut buf = String::new();
         file.read_to_string(&mut buf).unwrap();
-        if !buf.contains("[features]") {
-            file.write_all(include_str!("templates/Cargo.toml.feature-patch.template").as_bytes())
+        if let Some(feature) = logger.feature() {
+            if !buf.contains("[features]") {
+                file.write_all(
+                    format!(
+                        include_str!("templates/Cargo.toml.feature-patch.template"),
+                        feature = feature
+                    )
+                    .as_bytes(),
+                )
                 .map_err(|_| Error::CreateFile("Cargo.toml".into()))?;
+            }
         }
 
         file.write_all(
@@ -245,33 +415,78 @@ impl Init {
         self.create_file("src/fmt.rs", include_str!("templates/fmt.rs.template"))
     }
 
+    fn init_trace(&self, trace: Option<&Trace>) -> Result<(), Error> {
+        if trace.is_none() {
+            return Ok(());
+        }
+
+        self.create_file("src/trace.rs", include_str!("templates/trace.rs.template"))
+    }
+
     fn init_main(
         &self,
         family: &Family,
         panic_handler: &PanicHandler,
         softdevice: Option<&Softdevice>,
+        bootloader: bool,
+        trace: Option<&Trace>,
+        ble_role: BleRole,
     ) -> Result<(), Error> {
         let panic_handler = to_snake_case(panic_handler.str());
 
+        // `mod trace;` plus its call to initialize the SystemView target
+        // before the executor spawns anything -- both no-ops when untraced.
+        let (trace_mod, trace_init) = match trace {
+            Some(_) => ("mod trace;\n", "trace::init();\n    "),
+            None => ("", ""),
+        };
+
         self.create_file(
             "src/main.rs",
             &match (family, softdevice) {
                 (Family::STM32, _) => format!(
                     include_str!("templates/main.rs.stm32.template"),
-                    panic_handler = panic_handler
+                    panic_handler = panic_handler,
+                    trace_mod = trace_mod,
+                    trace_init = trace_init,
+                ),
+                (Family::NRF(_), _) if bootloader => format!(
+                    include_str!("templates/main.rs.nrf.bootloader.template"),
+                    panic_handler = panic_handler,
+                    trace_mod = trace_mod,
+                    trace_init = trace_init,
+                ),
+                (Family::NRF(_), Some(_)) if ble_role == BleRole::Central => format!(
+                    include_str!("templates/main.rs.nrf.sd.central.template"),
+                    panic_handler = panic_handler,
+                    trace_mod = trace_mod,
+                    trace_init = trace_init,
                 ),
                 (Family::NRF(_), Some(_)) => {
+                    // Peripheral starter also covers `--ble-role both`: the
+                    // Cargo features for the central role are on, but the
+                    // example app itself only needs to pick one starting point.
                     format!(
                         include_str!("templates/main.rs.nrf.sd.template"),
-                        panic_handler = panic_handler
+                        panic_handler = panic_handler,
+                        trace_mod = trace_mod,
+                        trace_init = trace_init,
                     )
                 }
                 (Family::NRF(_), None) => {
                     format!(
                         include_str!("templates/main.rs.nrf.template"),
-                        panic_handler = panic_handler
+                        panic_handler = panic_handler,
+                        trace_mod = trace_mod,
+                        trace_init = trace_init,
                     )
                 }
+                (Family::RP, _) => format!(
+                    include_str!("templates/main.rs.rp.template"),
+                    panic_handler = panic_handler,
+                    trace_mod = trace_mod,
+                    trace_init = trace_init,
+                ),
             },
         )
     }
@@ -289,6 +504,66 @@ impl Init {
         )
     }
 
+    /// Scaffold a companion `embassy-boot` bootloader crate in `./bootloader`,
+    /// flashed separately from (and ahead of) the app.
+    fn init_bootloader(&self, name: &str, chip: &Chip, partitions: &Partitions) -> Result<(), Error> {
+        self.pb.set_message("Create bootloader project");
+        Command::new("cargo")
+            .args(["new", "bootloader"])
+            .output()
+            .map_err(|_| Error::CreateCargo)?;
+
+        set_current_dir("bootloader").map_err(|_| Error::ChangeDir)?;
+
+        self.create_file(
+            "memory.x",
+            &format!(
+                include_str!("templates/memory.x.template"),
+                flash_origin = partitions.bootloader.flash_origin,
+                flash_len = partitions.bootloader.flash_length,
+                ram_origin = partitions.bootloader.ram_origin,
+                ram_len = partitions.bootloader.ram_length,
+            ),
+        )?;
+
+        self.create_file(
+            "build.rs",
+            &format!(
+                include_str!("templates/build.rs.nrf.template"),
+                defmt_link_arg = ""
+            ),
+        )?;
+
+        self.create_file(
+            "Cargo.toml",
+            &format!(
+                include_str!("templates/Cargo.toml.bootloader.template"),
+                name = format!("{name}-bootloader"),
+            ),
+        )?;
+
+        self.cargo_add_pinned("embassy-boot-nrf", None, false, Some("0.2"))?;
+        self.cargo_add("embassy-nrf", Some(&[chip.name.as_str()]), false)?;
+        self.cargo_add("cortex-m", Some(&["inline-asm"]), false)?;
+        self.cargo_add("cortex-m-rt", None, false)?;
+        self.cargo_add("panic-reset", None, false)?;
+
+        self.create_file(
+            "src/main.rs",
+            &format!(
+                include_str!("templates/main.rs.bootloader.template"),
+                active_origin = format!("{:#x}", partitions.active.flash_origin),
+                active_len = format!("{:#x}", partitions.active.flash_length),
+                dfu_origin = format!("{:#x}", partitions.dfu.flash_origin),
+                dfu_len = format!("{:#x}", partitions.dfu.flash_length),
+                state_origin = format!("{:#x}", partitions.bootloader_state.flash_origin),
+                state_len = format!("{:#x}", partitions.bootloader_state.flash_length),
+            ),
+        )?;
+
+        set_current_dir("..").map_err(|_| Error::ChangeDir)
+    }
+
     fn create_file(&self, name: &str, content: &str) -> Result<(), Error> {
         self.pb.set_message(format!("Create file: {name}"));
 
@@ -310,20 +585,44 @@ impl Init {
         name: &str,
         features: Option<&[&str]>,
         optional: bool,
+    ) -> Result<(), Error> {
+        self.cargo_add_pinned(name, features, optional, None)
+    }
+
+    /// Like [`Self::cargo_add`], but pins the dependency to `version` instead of
+    /// letting `cargo add` resolve the latest release -- needed where our
+    /// generated templates target a specific (pre-1.0) API shape that has
+    /// since moved on.
+    fn cargo_add_pinned(
+        &self,
+        name: &str,
+        features: Option<&[&str]>,
+        optional: bool,
+        version: Option<&str>,
     ) -> Result<(), Error> {
         self.pb.set_message(format!("Cargo add: {name}"));
 
         let features = features.unwrap_or_default().join(",");
         let mut cmd = Command::new("cargo");
 
+        // `cargo add` takes a version requirement as `<name>@<version>`.
+        let dep = match version {
+            Some(version) => format!("{name}@{version}"),
+            None => name.to_owned(),
+        };
+
         cmd.arg("add")
-            .args([name, &format!("--features={features}")]);
+            .args([&dep, &format!("--features={features}")]);
 
         if optional {
             cmd.arg("--optional");
         }
 
-        cmd.output().map_err(|_| Error::CargoAdd(name.into()))?;
+        let output = cmd.output().map_err(|_| Error::CargoAdd(name.into()))?;
+
+        if !output.status.success() {
+            return Err(Error::CargoAdd(name.into()));
+        }
 
         Ok(())
     }
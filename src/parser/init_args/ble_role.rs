@@ -0,0 +1,25 @@
+use clap::ValueEnum;
+
+/// BLE role the generated Softdevice project plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BleRole {
+    Peripheral,
+    Central,
+    Both,
+}
+
+impl BleRole {
+    /// `nrf-softdevice` features needed to support this role.
+    pub fn features(&self) -> &'static [&'static str] {
+        match self {
+            Self::Peripheral => &["ble-peripheral", "ble-gatt-server"],
+            Self::Central => &["ble-central", "ble-gatt-client"],
+            Self::Both => &[
+                "ble-peripheral",
+                "ble-gatt-server",
+                "ble-central",
+                "ble-gatt-client",
+            ],
+        }
+    }
+}
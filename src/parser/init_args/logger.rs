@@ -0,0 +1,34 @@
+use clap::ValueEnum;
+
+/// Logging/formatting backend wired into the generated `src/fmt.rs`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Logger {
+    DefmtRtt,
+    Log,
+    None,
+}
+
+impl Logger {
+    pub fn str(&self) -> &'static str {
+        match self {
+            Self::DefmtRtt => "defmt-rtt",
+            Self::Log => "log",
+            Self::None => "none",
+        }
+    }
+
+    /// Whether this backend needs RTT wired up in `.cargo/config.toml` and `Embed.toml`.
+    pub fn needs_rtt(&self) -> bool {
+        matches!(self, Self::DefmtRtt)
+    }
+
+    /// Cargo feature that must be default-enabled for this backend's
+    /// `info!`/`warn!`/... macros in `fmt.rs` to actually log anything.
+    pub fn feature(&self) -> Option<&'static str> {
+        match self {
+            Self::DefmtRtt => Some("defmt"),
+            Self::Log => Some("log"),
+            Self::None => None,
+        }
+    }
+}
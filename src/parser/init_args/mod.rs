@@ -0,0 +1,58 @@
+pub mod ble_role;
+pub mod logger;
+pub mod panic_handler;
+pub mod soft_device;
+pub mod tick_hz;
+pub mod trace;
+
+use clap::Args;
+
+pub use ble_role::BleRole;
+pub use logger::Logger;
+pub use panic_handler::PanicHandler;
+pub use soft_device::Softdevice;
+pub use tick_hz::TickHz;
+pub use trace::Trace;
+
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    /// Name of the project to create
+    pub name: String,
+
+    /// Target chip, e.g. "nrf52840" or "stm32f405rg"
+    #[arg(long)]
+    pub chip_name: String,
+
+    /// Panic handling strategy to scaffold the project with
+    #[arg(long, value_enum, default_value = "panic-probe")]
+    pub panic_handler: PanicHandler,
+
+    /// Add nrf-softdevice support for the given Softdevice variant
+    #[arg(long, value_enum)]
+    pub softdevice: Option<Softdevice>,
+
+    /// BLE role the generated project plays, defaulting to peripheral (requires --softdevice)
+    #[arg(long, value_enum)]
+    pub ble_role: Option<BleRole>,
+
+    /// Scaffold an embassy-boot bootloader + OTA firmware-updater alongside the app
+    #[arg(long)]
+    pub bootloader: bool,
+
+    /// Logging/formatting backend to wire `src/fmt.rs` up against
+    #[arg(long, value_enum, default_value = "defmt-rtt")]
+    pub logger: Logger,
+
+    /// `embassy-time` tick rate
+    #[arg(long, value_enum, default_value = "hz32768")]
+    pub tick_hz: TickHz,
+
+    /// Time-driver peripheral to back embassy-time with, e.g. "any", "rtc0", "tim2"
+    /// (defaults to the family's usual choice; embassy-rp has none to pick from)
+    #[arg(long)]
+    pub time_driver: Option<String>,
+
+    /// Wire the generated executor up for rtos-trace / SystemView task tracing
+    #[arg(long, value_enum)]
+    pub trace: Option<Trace>,
+}
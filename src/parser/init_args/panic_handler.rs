@@ -0,0 +1,19 @@
+use clap::ValueEnum;
+
+/// Crate used to implement `#[panic_handler]` in the generated project.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PanicHandler {
+    PanicProbe,
+    PanicReset,
+    PanicHalt,
+}
+
+impl PanicHandler {
+    pub fn str(&self) -> &'static str {
+        match self {
+            Self::PanicProbe => "panic-probe",
+            Self::PanicReset => "panic-reset",
+            Self::PanicHalt => "panic-halt",
+        }
+    }
+}
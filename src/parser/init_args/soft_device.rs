@@ -0,0 +1,23 @@
+use clap::ValueEnum;
+
+/// Nordic Softdevice variant to link the generated project against.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Softdevice {
+    S112,
+    S113,
+    S122,
+    S132,
+    S140,
+}
+
+impl Softdevice {
+    pub fn str(&self) -> &'static str {
+        match self {
+            Self::S112 => "s112",
+            Self::S113 => "s113",
+            Self::S122 => "s122",
+            Self::S132 => "s132",
+            Self::S140 => "s140",
+        }
+    }
+}
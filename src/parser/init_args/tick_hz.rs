@@ -0,0 +1,23 @@
+use clap::ValueEnum;
+
+/// `embassy-time` tick rate, i.e. the resolution `Instant`/`Duration` are counted in.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum TickHz {
+    Hz1,
+    Hz100,
+    Hz1000,
+    Hz32768,
+    Hz1000000,
+}
+
+impl TickHz {
+    pub fn str(&self) -> &'static str {
+        match self {
+            Self::Hz1 => "tick-hz-1",
+            Self::Hz100 => "tick-hz-100",
+            Self::Hz1000 => "tick-hz-1_000",
+            Self::Hz32768 => "tick-hz-32_768",
+            Self::Hz1000000 => "tick-hz-1_000_000",
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use clap::ValueEnum;
+
+/// Runtime task-tracing backend wired into the generated executor.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Trace {
+    RtosTrace,
+}
+
+impl Trace {
+    pub fn str(&self) -> &'static str {
+        match self {
+            Self::RtosTrace => "rtos-trace",
+        }
+    }
+}
@@ -0,0 +1,20 @@
+pub mod init_args;
+
+use clap::{Parser, Subcommand};
+
+pub use init_args::InitArgs;
+
+#[derive(Debug, Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+pub enum Cargo {
+    #[command(subcommand)]
+    Embassy(EmbassyCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum EmbassyCommand {
+    /// Scaffold a new Embassy project
+    Init(InitArgs),
+    /// Open the Embassy documentation
+    Docs,
+}